@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Write};
+use tokio::sync::mpsc;
+
+/// A writer that forwards logged bytes to `sender` (typically a channel read by a UI), falling
+/// back to stdout when there is no sender, i.e. the display is disabled.
+pub struct LogWriter {
+    sender: Option<mpsc::Sender<Vec<u8>>>,
+}
+
+impl LogWriter {
+    pub fn new(sender: &Option<mpsc::Sender<Vec<u8>>>) -> Self {
+        Self { sender: sender.clone() }
+    }
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &self.sender {
+            Some(sender) => {
+                let _ = sender.try_send(buf.to_vec());
+                Ok(buf.len())
+            }
+            None => io::stdout().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
@@ -0,0 +1,4 @@
+pub mod log_writer;
+pub mod logger;
+
+pub use log_writer::LogWriter;
@@ -0,0 +1,39 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_cli::helpers::logger::{
+    initialize_logger_with_format, notification_message, set_verbosity, welcome_message, LogFormat, RotationPolicy,
+};
+
+#[tokio::main]
+async fn main() {
+    println!("{}", welcome_message());
+    println!("{}", notification_message());
+
+    // `_worker_guard` must stay alive for the process to keep flushing buffered logfile lines,
+    // and `verbosity_handle` lets the node raise or lower its tracing verbosity at runtime (e.g.
+    // from a signal handler or a control channel) without a restart.
+    let (_log_receiver, verbosity_handle, _worker_guard) = initialize_logger_with_format(
+        0,
+        false,
+        "snarkos.log",
+        LogFormat::Json,
+        RotationPolicy::default(),
+        None,
+        None,
+    );
+    let _ = set_verbosity(&verbosity_handle, 0);
+
+    // ... node startup continues here, for as long as `_worker_guard` remains in scope ...
+}